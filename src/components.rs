@@ -0,0 +1,91 @@
+use specs::prelude::*;
+use specs_derive::Component;
+use rltk::{ RGB, Point };
+
+/// A position with x and y coordinates.
+///
+/// Note that without the derive macro, you would do:
+///
+/// ```rust
+/// // The ECS is storing the component.
+/// impl Component for Position {
+///     type Storage = VecStorage<Self>;
+/// }
+/// ```
+#[derive(Component)]
+pub struct Position {
+    pub x: i32,
+    pub y: i32,
+}
+
+/// How to draw the entity.
+#[derive(Component)]
+pub struct Renderable {
+    pub glyph: u8,
+    pub fg: RGB,
+    pub bg: RGB,
+}
+
+#[derive(Component, Debug)]
+pub struct Player {}
+
+/// What an entity can currently see.
+///
+/// `dirty` is set whenever the entity moves, so `VisibilitySystem` only
+/// redoes the (relatively expensive) shadowcasting for entities whose view
+/// could actually have changed.
+#[derive(Component)]
+pub struct Viewshed {
+    pub visible_tiles: Vec<Point>,
+    pub range: i32,
+    pub dirty: bool,
+}
+
+/// Marks an entity as a hostile creature driven by `MonsterAI`.
+#[derive(Component, Debug)]
+pub struct Monster {}
+
+/// A displayable name, e.g. for combat messages.
+#[derive(Component, Debug)]
+pub struct Name {
+    pub name: String,
+}
+
+/// Marks an entity that occupies its tile, preventing others from moving
+/// into it. Consulted by `MapIndexingSystem`.
+#[derive(Component, Debug)]
+pub struct BlocksTile {}
+
+/// Hit points and the numbers that drive melee combat.
+#[derive(Component, Debug)]
+pub struct CombatStats {
+    pub max_hp: i32,
+    pub hp: i32,
+    pub defense: i32,
+    pub power: i32,
+}
+
+/// Filed by `MonsterAI`/`player_input` when an entity wants to attack
+/// `target` this turn; consumed by `MeleeCombatSystem`.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct WantsToMelee {
+    pub target: Entity,
+}
+
+/// Queued, unapplied damage. Multiple hits in one turn just push more
+/// entries; `DamageSystem` sums them and clears the vec.
+#[derive(Component, Debug)]
+pub struct SufferDamage {
+    pub amount: Vec<i32>,
+}
+
+impl SufferDamage {
+    pub fn new_damage(store: &mut WriteStorage<SufferDamage>, victim: Entity, amount: i32) {
+        if let Some(suffering) = store.get_mut(victim) {
+            suffering.amount.push(amount);
+        } else {
+            let dmg = SufferDamage { amount: vec![amount] };
+            store.insert(victim, dmg).expect("Unable to insert damage");
+        }
+    }
+}
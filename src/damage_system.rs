@@ -0,0 +1,44 @@
+use specs::prelude::*;
+use super::{ CombatStats, Player, SufferDamage };
+
+/// Applies queued `SufferDamage` to `CombatStats::hp` and clears the queue.
+pub struct DamageSystem {}
+
+impl<'a> System<'a> for DamageSystem {
+    type SystemData = ( WriteStorage<'a, CombatStats>,
+                         WriteStorage<'a, SufferDamage> );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (mut stats, mut damage) = data;
+
+        for (stats, damage) in (&mut stats, &damage).join() {
+            stats.hp -= damage.amount.iter().sum::<i32>();
+        }
+
+        damage.clear();
+    }
+}
+
+/// Removes any entity whose `hp` has dropped to zero or below. If that
+/// entity is the player, it is not deleted: "You are dead" is logged to the
+/// console and play carries on; the game does not end.
+pub fn delete_the_dead(ecs: &mut World) {
+    let mut dead: Vec<Entity> = Vec::new();
+    {
+        let combat_stats = ecs.read_storage::<CombatStats>();
+        let players = ecs.read_storage::<Player>();
+        let entities = ecs.entities();
+        for (entity, stats) in (&entities, &combat_stats).join() {
+            if stats.hp < 1 {
+                match players.get(entity) {
+                    None => dead.push(entity),
+                    Some(_) => rltk::console::log("You are dead"),
+                }
+            }
+        }
+    }
+
+    for victim in dead {
+        ecs.delete_entity(victim).expect("Unable to delete dead entity");
+    }
+}
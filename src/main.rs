@@ -3,15 +3,38 @@ use specs::prelude::*;
 #[macro_use]
 extern crate specs_derive;
 
-use rltk::{ Console, GameState, Rltk, RGB };
+use rltk::{ GameState, Point, Rltk, RGB };
 mod components;
 pub use components::*;
 mod map;
 pub use map::*;
+mod map_builders;
+use map_builders::{ BuilderChain, RoomsAndCorridorsBuilder, RoomCornerRounder, WallSmoother };
 mod player;
 use player::*;
 mod rect;
-use rect::Rect;
+pub use rect::Rect;
+mod rng;
+pub use rng::RngResource;
+mod visibility_system;
+use visibility_system::VisibilitySystem;
+mod monster_ai_system;
+use monster_ai_system::MonsterAI;
+mod melee_combat_system;
+use melee_combat_system::MeleeCombatSystem;
+mod damage_system;
+use damage_system::DamageSystem;
+mod map_indexing_system;
+use map_indexing_system::MapIndexingSystem;
+
+/// Drives whose turn it is: the player waits for a key, then both the
+/// player's and every monster's actions resolve before control returns.
+#[derive(PartialEq, Copy, Clone)]
+pub enum RunState {
+    AwaitingInput,
+    PlayerTurn,
+    MonsterTurn,
+}
 
 /// `World` comes from the `Specs` crate.
 pub struct State {
@@ -20,51 +43,51 @@ pub struct State {
 
 impl State {
     fn run_systems(&mut self) {
+        let mut vis = VisibilitySystem {};
+        vis.run_now(&self.ecs);
+        let mut mapindex = MapIndexingSystem {};
+        mapindex.run_now(&self.ecs);
+        let mut mob = MonsterAI {};
+        mob.run_now(&self.ecs);
+        let mut melee = MeleeCombatSystem {};
+        melee.run_now(&self.ecs);
+        let mut damage = DamageSystem {};
+        damage.run_now(&self.ecs);
+
         // "tells Specs that if any changes were queued up by the
         // systems, they should apply to the world now."
         self.ecs.maintain();
     }
 }
 
-
-/// A position with x and y coordinates.
-///
-/// Note that without the derive macro, you would do:
-///
-/// ```rust
-/// // The ECS is storing the component.
-/// impl Component for Position {
-///     type Storage = VecStorage<Self>;
-/// }
-/// ```
-#[derive(Component)]
-struct Position {
-    x: i32,
-    y: i32,
-}
-
-/// How to draw the entity.
-#[derive(Component)]
-struct Renderable {
-    glyph: u8,
-    fg: RGB,
-    bg: RGB,
-}
-
-#[derive(Component, Debug)]
-struct Player {}
-
 impl GameState for State {
     fn tick(&mut self, ctx: &mut Rltk) {
         ctx.cls();
 
-        player_input(self, ctx);
-        self.run_systems();
+        let mut newrunstate = *self.ecs.fetch::<RunState>();
+
+        match newrunstate {
+            RunState::AwaitingInput => {
+                newrunstate = player_input(self, ctx);
+            }
+            RunState::PlayerTurn => {
+                self.run_systems();
+                newrunstate = RunState::MonsterTurn;
+            }
+            RunState::MonsterTurn => {
+                self.run_systems();
+                newrunstate = RunState::AwaitingInput;
+            }
+        }
+
+        *self.ecs.write_resource::<RunState>() = newrunstate;
+
+        damage_system::delete_the_dead(&mut self.ecs);
 
         // `fetch` will crash if the resource doesn't exist. It's a
         // `shred` type, which usually acts like a reference, but needs
         // coercing to actually become a reference.
-        let map = self.ecs.fetch::<Vec<TileType>>();
+        let map = self.ecs.fetch::<Map>();
         draw_map(&map, ctx);
 
         let positions = self.ecs.read_storage::<Position>();
@@ -89,12 +112,49 @@ fn main() {
     gs.ecs.register::<Position>();
     gs.ecs.register::<Renderable>();
     gs.ecs.register::<Player>();
+    gs.ecs.register::<Viewshed>();
+    gs.ecs.register::<Monster>();
+    gs.ecs.register::<Name>();
+    gs.ecs.register::<BlocksTile>();
+    gs.ecs.register::<CombatStats>();
+    gs.ecs.register::<WantsToMelee>();
+    gs.ecs.register::<SufferDamage>();
+
+    let mut rng = RngResource::new(None);
+    let mut builder = BuilderChain::new(80, 50);
+    builder.start_with(RoomsAndCorridorsBuilder::new());
+    builder.with(RoomCornerRounder::new());
+    builder.with(WallSmoother::new(4));
+    builder.build_map(&mut rng);
+    let start_pos = builder.build_data.starting_position.as_ref().unwrap();
+    let (player_x, player_y) = (start_pos.x, start_pos.y);
+
+    for (i, room) in builder.build_data.rooms.iter().skip(1).enumerate() {
+        let (x, y) = room.center();
+
+        let (glyph, name) = if rng.roll_dice(1, 2) == 1 {
+            (rltk::to_cp437('g'), "Goblin")
+        } else {
+            (rltk::to_cp437('o'), "Orc")
+        };
+
+        gs.ecs
+            .create_entity()
+            .with(Position { x, y })
+            .with(Renderable {
+                glyph,
+                fg: RGB::named(rltk::RED),
+                bg: RGB::named(rltk::BLACK),
+            })
+            .with(Viewshed { visible_tiles: Vec::new(), range: 8, dirty: true })
+            .with(Monster {})
+            .with(Name { name: format!("{} #{}", name, i) })
+            .with(BlocksTile {})
+            .with(CombatStats { max_hp: 16, hp: 16, defense: 1, power: 4 })
+            .build();
+    }
 
-    let (rooms, map) = new_map_rooms_and_corridors();
-    gs.ecs.insert(map);
-    let (player_x, player_y) = rooms[0].center();
-
-    gs.ecs
+    let player_entity = gs.ecs
         .create_entity()
         .with(Position { x: player_x, y: player_y })
         .with(Renderable {
@@ -103,7 +163,23 @@ fn main() {
             bg: RGB::named(rltk::BLACK),
         })
         .with(Player {})
+        .with(Viewshed { visible_tiles: Vec::new(), range: 8, dirty: true })
+        .with(Name { name: "Player".to_string() })
+        .with(CombatStats { max_hp: 30, hp: 30, defense: 2, power: 5 })
+        .with(BlocksTile {})
         .build();
 
+    gs.ecs.insert(builder.build_data.map);
+    gs.ecs.insert(Point::new(player_x, player_y));
+    gs.ecs.insert(player_entity);
+    gs.ecs.insert(RunState::AwaitingInput);
+    gs.ecs.insert(rng);
+
+    // Populate `blocked`/`tile_content` before the first frame, so the very
+    // first keypress already sees accurate occupancy instead of an empty index.
+    let mut mapindex = MapIndexingSystem {};
+    mapindex.run_now(&gs.ecs);
+    gs.ecs.maintain();
+
     rltk::main_loop(context, gs);
 }
@@ -1,5 +1,6 @@
-use rltk::{ RGB, Rltk, Console, RandomNumberGenerator };
-use super::{ Rect };
+use rltk::{ Algorithm2D, BaseMap, Console, DistanceAlg, Point, RGB, Rltk, SmallVec };
+use specs::Entity;
+use super::Rect;
 use std::cmp::{ min, max };
 
 /// Represents a tile type.
@@ -12,143 +13,159 @@ pub enum TileType {
     Wall, Floor
 }
 
-/// Find the index of the game map for x, y.
+/// The game map: its tiles, the rooms carved into it, and its dimensions.
 ///
-/// The map is a 4000-item vector. (80*50)
-pub fn xy_idx(x: i32, y: i32) -> usize {
-    (y as usize * 80) + x as usize
+/// Keeping these together (instead of passing around a bare `Vec<TileType>`)
+/// gives us one place to hang per-tile metadata, like the revealed/blocked
+/// bitvectors added in later chapters.
+#[derive(Clone)]
+pub struct Map {
+    pub tiles: Vec<TileType>,
+    pub rooms: Vec<Rect>,
+    pub width: i32,
+    pub height: i32,
+    pub revealed_tiles: Vec<bool>,
+    pub visible_tiles: Vec<bool>,
+    pub blocked: Vec<bool>,
+    pub tile_content: Vec<Vec<Entity>>,
 }
 
-/// Create a new game map with solid boundaries and 400 randomly placed
-/// walls.
-pub fn new_map_test() -> Vec<TileType> {
-    let mut map = vec![TileType::Floor; 80*50];
+impl Map {
+    /// An empty, all-wall map of the given dimensions. Map builders carve it
+    /// up from here.
+    pub fn new(width: i32, height: i32) -> Map {
+        let tile_count = (width * height) as usize;
+        Map {
+            tiles: vec![TileType::Wall; tile_count],
+            rooms: Vec::new(),
+            width,
+            height,
+            revealed_tiles: vec![false; tile_count],
+            visible_tiles: vec![false; tile_count],
+            blocked: vec![false; tile_count],
+            tile_content: vec![Vec::new(); tile_count],
+        }
+    }
 
-    // Make the boundary walls
-    for x in 0..80 {
-        map[xy_idx(x, 0)] = TileType::Wall;
-        map[xy_idx(x, 49)] = TileType::Wall;
+    /// Recomputes `blocked` from the tile grid. `MapIndexingSystem` calls
+    /// this each turn before layering in `BlocksTile` entities.
+    pub fn populate_blocked(&mut self) {
+        for i in 0..self.tiles.len() {
+            self.blocked[i] = self.tiles[i] == TileType::Wall;
+        }
     }
 
-    for y in 0..50 {
-        map[xy_idx(0, y)] = TileType::Wall;
-        map[xy_idx(79, y)] = TileType::Wall;
+    /// Empties `tile_content` without shrinking it, ready to be repopulated
+    /// for this turn.
+    pub fn clear_content_index(&mut self) {
+        for content in self.tile_content.iter_mut() {
+            content.clear();
+        }
     }
 
-    // Randomly splat a bunch of walls.
-    let mut rng = rltk::RandomNumberGenerator::new();
+    /// Whether an entity could step onto (x, y): in bounds and not blocked.
+    pub fn is_exit_valid(&self, x: i32, y: i32) -> bool {
+        if x < 0 || x >= self.width || y < 0 || y >= self.height { return false; }
+        !self.blocked[self.xy_idx(x, y)]
+    }
 
-    for _i in 0..400 {
-        // roll 1d79
-        let x = rng.roll_dice(1, 79);
-        let y = rng.roll_dice(1, 49);
-        let idx = xy_idx(x, y);
-        if idx != xy_idx(40, 25) {
-            map[idx] = TileType::Wall;
-        }
+    /// Find the index of the game map for x, y.
+    pub fn xy_idx(&self, x: i32, y: i32) -> usize {
+        (y as usize * self.width as usize) + x as usize
     }
 
-    map
-}
+    /// Set all the points on the map to Floor tiles.
+    pub(crate) fn apply_room_to_map(&mut self, room: &Rect) {
+        // `..=` means an inclusive range
+        for y in room.y1 + 1 ..= room.y2 {
+            for x in room.x1 + 1 ..= room.x2 {
+                let idx = self.xy_idx(x, y);
+                self.tiles[idx] = TileType::Floor;
+            }
+        }
+    }
 
-/// Set all the points on the map to Floor tiles.
-fn apply_room_to_map(room: &Rect, map: &mut [TileType]) {
-    // `..=` means an inclusive range
-    for y in room.y1 + 1 ..= room.y2 {
-        for x in room.x1 + 1 ..= room.x2 {
-            map[xy_idx(x, y)] = TileType::Floor;
+    pub(crate) fn apply_horizontal_tunnel(&mut self, x1: i32, x2: i32, y: i32) {
+        for x in min(x1, x2) ..= max(x1, x2) {
+            let idx = self.xy_idx(x, y);
+            if idx > 0 && idx < (self.width as usize * self.height as usize) {
+                self.tiles[idx] = TileType::Floor;
+            }
         }
     }
-}
 
-fn apply_horizontal_tunnel(map: &mut [TileType], x1: i32, x2: i32, y: i32) {
-    for x in min(x1, x2) ..= max(x1, x2) {
-        let idx = xy_idx(x, y);
-        if idx > 0 && idx < 80 * 50 {
-            map[idx as usize] = TileType::Floor;
+    pub(crate) fn apply_vertical_tunnel(&mut self, y1: i32, y2: i32, x: i32) {
+        for y in min(y1, y2) ..= max(y1, y2) {
+            let idx = self.xy_idx(x, y);
+            if idx > 0 && idx < (self.width as usize * self.height as usize) {
+                self.tiles[idx] = TileType::Floor;
+            }
         }
     }
 }
 
-fn apply_vertical_tunnel(map: &mut [TileType], y1: i32, y2: i32, x: i32) {
-    for y in min(y1, y2) ..= max(y1, y2) {
-        let idx = xy_idx(x, y);
-        if idx > 0 && idx < 80 * 50 {
-            map[idx as usize] = TileType::Floor;
-        }
+impl Algorithm2D for Map {
+    fn dimensions(&self) -> Point {
+        Point::new(self.width, self.height)
     }
 }
 
-pub fn new_map_rooms_and_corridors() -> (Vec<Rect>, Vec<TileType>) {
-    let mut map = vec![TileType::Wall; 80*50];
-
-    let mut rooms: Vec<Rect> = Vec::new();
-    const MAX_ROOMS: i32 = 30;
-    const MIN_SIZE: i32 = 6;
-    const MAX_SIZE: i32 = 10;
-
-    let mut rng = RandomNumberGenerator::new();
-
-    for _i in 0..MAX_ROOMS {
-        let w =  rng.range(MIN_SIZE, MAX_SIZE);
-        let h = rng.range(MIN_SIZE, MAX_SIZE);
-        let x = rng.roll_dice(1, 80 - w - 1) - 1;
-        let y = rng.roll_dice(1, 50 - h - 1) - 1;
-        let new_room = Rect::new(x, y, w, h);
-        let mut ok = true;
-        for other_room in rooms.iter() {
-            if new_room.intersect(other_room) { ok = false }
-        }
-        if ok {
-            apply_room_to_map(&new_room, &mut map);
-
-            if !rooms.is_empty() {
-                let (new_x, new_y) = new_room.center();
-                let (prev_x, prev_y) = rooms[rooms.len() - 1].center();
-                if rng.range(0, 2) == 1 {
-                    apply_horizontal_tunnel(&mut map, prev_x, new_x, prev_y);
-                    apply_horizontal_tunnel(&mut map, prev_y, prev_y, new_x);
-                } else {
-                    apply_vertical_tunnel(&mut map, prev_y, new_y, prev_x);
-                    apply_vertical_tunnel(&mut map, prev_x, prev_x, new_y);
-                }
-            }
+impl BaseMap for Map {
+    fn is_opaque(&self, idx: usize) -> bool {
+        self.tiles[idx] == TileType::Wall
+    }
 
-            rooms.push(new_room);
-        }
+    /// Used by `rltk::a_star_search` for monster pathfinding.
+    fn get_available_exits(&self, idx: usize) -> SmallVec<[(usize, f32); 10]> {
+        let mut exits = SmallVec::new();
+        let x = idx as i32 % self.width;
+        let y = idx as i32 / self.width;
+        let w = self.width as usize;
+
+        if self.is_exit_valid(x - 1, y) { exits.push((idx - 1, 1.0)) }
+        if self.is_exit_valid(x + 1, y) { exits.push((idx + 1, 1.0)) }
+        if self.is_exit_valid(x, y - 1) { exits.push((idx - w, 1.0)) }
+        if self.is_exit_valid(x, y + 1) { exits.push((idx + w, 1.0)) }
+
+        exits
+    }
+
+    fn get_pathing_distance(&self, idx1: usize, idx2: usize) -> f32 {
+        DistanceAlg::Pythagoras.distance2d(self.index_to_point2d(idx1), self.index_to_point2d(idx2))
     }
-    (rooms, map)
 }
 
 /// Draw the map.
 ///
-/// The tutorial author said the he passes in `&[TileType]` instead of
-/// `&Vec<TileType>` in order to pass in slices of a map, if necessary.
-pub fn draw_map(map: &[TileType], ctx: &mut Rltk) {
+/// Tiles that have never been seen are skipped entirely; tiles that have
+/// been seen but are outside the current viewshed are drawn dimmed, giving
+/// the classic "fog of war" look.
+pub fn draw_map(map: &Map, ctx: &mut Rltk) {
     let mut y = 0;
     let mut x = 0;
-    for tile in map.iter() {
-        match tile {
-            TileType::Floor => {
-                // `to_cp437` converts unicode to DOX/CP437 char set. (â˜º' is 1.)
-                // http://dwarffortresswiki.org/index.php/Character_table
-                ctx.set(x, y, RGB::from_f32(0.5, 0.5, 0.5),
-                              RGB::from_f32(0., 0., 0.),
-                              rltk::to_cp437('.'));
-            }
-            TileType::Wall => {
-                ctx.set(x, y, RGB::from_f32(0.0, 1.0, 0.0),
-                              RGB::from_f32(0., 0., 0.),
-                              rltk::to_cp437('#'));
+    for (idx, tile) in map.tiles.iter().enumerate() {
+        if map.revealed_tiles[idx] {
+            let glyph;
+            let mut fg;
+            match tile {
+                TileType::Floor => {
+                    glyph = rltk::to_cp437('.');
+                    fg = RGB::from_f32(0.5, 0.5, 0.5);
+                }
+                TileType::Wall => {
+                    glyph = rltk::to_cp437('#');
+                    fg = RGB::from_f32(0.0, 1.0, 0.0);
+                }
             }
+            if !map.visible_tiles[idx] { fg = fg.to_greyscale(); }
+            ctx.set(x, y, fg, RGB::from_f32(0., 0., 0.), glyph);
         }
 
         // move the coordinates
         x += 1;
-        if x > 79 {
+        if x > map.width - 1 {
             x = 0;
             y += 1;
         }
     }
 }
-
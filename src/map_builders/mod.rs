@@ -0,0 +1,81 @@
+use super::{ Map, Position, Rect, RngResource };
+
+mod simple_map;
+pub use simple_map::RoomsAndCorridorsBuilder;
+mod random_splat;
+pub use random_splat::RandomSplatBuilder;
+mod room_corner_rounder;
+pub use room_corner_rounder::RoomCornerRounder;
+mod wall_smoother;
+pub use wall_smoother::WallSmoother;
+
+/// The in-progress state threaded through a `BuilderChain`: the map being
+/// carved, the rooms found so far, where the player should start, and a
+/// snapshot of the tile grid after each step (for animating generation).
+pub struct BuilderMap {
+    pub map: Map,
+    pub rooms: Vec<Rect>,
+    pub starting_position: Option<Position>,
+    pub history: Vec<Map>,
+}
+
+/// Produces a map from nothing - the first step in a `BuilderChain`.
+pub trait InitialMapBuilder {
+    fn build_map(&mut self, rng: &mut RngResource, build_data: &mut BuilderMap);
+}
+
+/// Mutates a map that an earlier builder already produced.
+pub trait MetaMapBuilder {
+    fn build_map(&mut self, rng: &mut RngResource, build_data: &mut BuilderMap);
+}
+
+/// A data-driven map generation pipeline: exactly one `InitialMapBuilder`
+/// followed by zero or more `MetaMapBuilder` passes, run in order.
+pub struct BuilderChain {
+    starter: Option<Box<dyn InitialMapBuilder>>,
+    builders: Vec<Box<dyn MetaMapBuilder>>,
+    pub build_data: BuilderMap,
+}
+
+impl BuilderChain {
+    pub fn new(width: i32, height: i32) -> BuilderChain {
+        BuilderChain {
+            starter: None,
+            builders: Vec::new(),
+            build_data: BuilderMap {
+                map: Map::new(width, height),
+                rooms: Vec::new(),
+                starting_position: None,
+                history: Vec::new(),
+            },
+        }
+    }
+
+    pub fn start_with(&mut self, starter: Box<dyn InitialMapBuilder>) {
+        match self.starter {
+            None => self.starter = Some(starter),
+            Some(_) => panic!("You can only have one starting builder."),
+        }
+    }
+
+    pub fn with(&mut self, metabuilder: Box<dyn MetaMapBuilder>) {
+        self.builders.push(metabuilder);
+    }
+
+    pub fn build_map(&mut self, rng: &mut RngResource) {
+        match &mut self.starter {
+            None => panic!("Cannot build a map without a starting builder."),
+            Some(starter) => starter.build_map(rng, &mut self.build_data),
+        }
+        self.take_snapshot();
+
+        for metabuilder in self.builders.iter_mut() {
+            metabuilder.build_map(rng, &mut self.build_data);
+            self.take_snapshot();
+        }
+    }
+
+    fn take_snapshot(&mut self) {
+        self.build_data.history.push(self.build_data.map.clone());
+    }
+}
@@ -0,0 +1,54 @@
+use super::{ BuilderMap, InitialMapBuilder };
+use super::super::{ Position, RngResource, TileType };
+
+/// A boundary-walled floor with a random scattering of single-tile walls.
+/// The original test map from before rooms-and-corridors existed, now just
+/// one more starting builder to choose from.
+pub struct RandomSplatBuilder {}
+
+impl InitialMapBuilder for RandomSplatBuilder {
+    fn build_map(&mut self, rng: &mut RngResource, build_data: &mut BuilderMap) {
+        self.splat(rng, build_data);
+    }
+}
+
+impl RandomSplatBuilder {
+    pub fn new() -> Box<RandomSplatBuilder> {
+        Box::new(RandomSplatBuilder {})
+    }
+
+    fn splat(&mut self, rng: &mut RngResource, build_data: &mut BuilderMap) {
+        let map = &mut build_data.map;
+        for tile in map.tiles.iter_mut() {
+            *tile = TileType::Floor;
+        }
+
+        for x in 0..map.width {
+            let top = map.xy_idx(x, 0);
+            let bottom = map.xy_idx(x, map.height - 1);
+            map.tiles[top] = TileType::Wall;
+            map.tiles[bottom] = TileType::Wall;
+        }
+        for y in 0..map.height {
+            let left = map.xy_idx(0, y);
+            let right = map.xy_idx(map.width - 1, y);
+            map.tiles[left] = TileType::Wall;
+            map.tiles[right] = TileType::Wall;
+        }
+
+        let center_x = map.width / 2;
+        let center_y = map.height / 2;
+        let center_idx = map.xy_idx(center_x, center_y);
+
+        for _i in 0..400 {
+            let x = rng.roll_dice(1, map.width - 2);
+            let y = rng.roll_dice(1, map.height - 2);
+            let idx = map.xy_idx(x, y);
+            if idx != center_idx {
+                map.tiles[idx] = TileType::Wall;
+            }
+        }
+
+        build_data.starting_position = Some(Position { x: center_x, y: center_y });
+    }
+}
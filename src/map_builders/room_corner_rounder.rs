@@ -0,0 +1,36 @@
+use super::{ BuilderMap, MetaMapBuilder };
+use super::super::{ Map, Rect, RngResource, TileType };
+
+/// Turns each room's four corner tiles back into wall, softening the sharp
+/// right angles that `RoomsAndCorridorsBuilder` leaves behind.
+pub struct RoomCornerRounder {}
+
+impl MetaMapBuilder for RoomCornerRounder {
+    fn build_map(&mut self, _rng: &mut RngResource, build_data: &mut BuilderMap) {
+        let rooms = build_data.rooms.clone();
+        for room in rooms.iter() {
+            self.round_corners(&mut build_data.map, room);
+        }
+    }
+}
+
+impl RoomCornerRounder {
+    pub fn new() -> Box<RoomCornerRounder> {
+        Box::new(RoomCornerRounder {})
+    }
+
+    fn round_corners(&mut self, map: &mut Map, room: &Rect) {
+        let corners = [
+            (room.x1 + 1, room.y1 + 1),
+            (room.x2, room.y1 + 1),
+            (room.x1 + 1, room.y2),
+            (room.x2, room.y2),
+        ];
+        for (x, y) in corners.iter() {
+            let idx = map.xy_idx(*x, *y);
+            if map.tiles[idx] == TileType::Floor {
+                map.tiles[idx] = TileType::Wall;
+            }
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use super::{ BuilderMap, InitialMapBuilder };
+use super::super::{ Position, Rect, RngResource };
+
+/// The classic "rooms and corridors" generator: place non-overlapping
+/// rectangular rooms and connect each to the previous one with an L-shaped
+/// corridor.
+pub struct RoomsAndCorridorsBuilder {}
+
+impl InitialMapBuilder for RoomsAndCorridorsBuilder {
+    fn build_map(&mut self, rng: &mut RngResource, build_data: &mut BuilderMap) {
+        self.rooms_and_corridors(rng, build_data);
+    }
+}
+
+impl RoomsAndCorridorsBuilder {
+    pub fn new() -> Box<RoomsAndCorridorsBuilder> {
+        Box::new(RoomsAndCorridorsBuilder {})
+    }
+
+    fn rooms_and_corridors(&mut self, rng: &mut RngResource, build_data: &mut BuilderMap) {
+        const MAX_ROOMS: i32 = 30;
+        const MIN_SIZE: i32 = 6;
+        const MAX_SIZE: i32 = 10;
+
+        for _i in 0..MAX_ROOMS {
+            let w = rng.range(MIN_SIZE, MAX_SIZE);
+            let h = rng.range(MIN_SIZE, MAX_SIZE);
+            let x = rng.roll_dice(1, build_data.map.width - w - 1) - 1;
+            let y = rng.roll_dice(1, build_data.map.height - h - 1) - 1;
+            let new_room = Rect::new(x, y, w, h);
+            let mut ok = true;
+            for other_room in build_data.rooms.iter() {
+                if new_room.intersect(other_room) { ok = false }
+            }
+            if ok {
+                build_data.map.apply_room_to_map(&new_room);
+
+                if !build_data.rooms.is_empty() {
+                    let (new_x, new_y) = new_room.center();
+                    let (prev_x, prev_y) = build_data.rooms[build_data.rooms.len() - 1].center();
+                    if rng.range(0, 2) == 1 {
+                        build_data.map.apply_horizontal_tunnel(prev_x, new_x, prev_y);
+                        build_data.map.apply_vertical_tunnel(prev_y, new_y, new_x);
+                    } else {
+                        build_data.map.apply_vertical_tunnel(prev_y, new_y, prev_x);
+                        build_data.map.apply_horizontal_tunnel(prev_x, new_x, new_y);
+                    }
+                }
+
+                build_data.rooms.push(new_room);
+            }
+        }
+
+        if let Some(start_room) = build_data.rooms.first() {
+            let (x, y) = start_room.center();
+            build_data.starting_position = Some(Position { x, y });
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use super::{ BuilderMap, MetaMapBuilder };
+use super::super::{ Map, RngResource, TileType };
+
+/// A single cellular-automata pass: any wall with fewer than `threshold` of
+/// its 8 neighbors also a wall becomes floor. Smooths out thin jagged walls
+/// left behind by earlier builders.
+pub struct WallSmoother {
+    threshold: i32,
+}
+
+impl MetaMapBuilder for WallSmoother {
+    fn build_map(&mut self, _rng: &mut RngResource, build_data: &mut BuilderMap) {
+        self.smooth(&mut build_data.map);
+    }
+}
+
+impl WallSmoother {
+    pub fn new(threshold: i32) -> Box<WallSmoother> {
+        Box::new(WallSmoother { threshold })
+    }
+
+    fn smooth(&mut self, map: &mut Map) {
+        let mut new_tiles = map.tiles.clone();
+
+        for y in 1..map.height - 1 {
+            for x in 1..map.width - 1 {
+                let idx = map.xy_idx(x, y);
+                if map.tiles[idx] == TileType::Wall && count_wall_neighbors(map, x, y) < self.threshold {
+                    new_tiles[idx] = TileType::Floor;
+                }
+            }
+        }
+
+        map.tiles = new_tiles;
+    }
+}
+
+fn count_wall_neighbors(map: &Map, x: i32, y: i32) -> i32 {
+    let mut count = 0;
+    for dy in -1..=1 {
+        for dx in -1..=1 {
+            if dx == 0 && dy == 0 { continue; }
+            let idx = map.xy_idx(x + dx, y + dy);
+            if map.tiles[idx] == TileType::Wall { count += 1; }
+        }
+    }
+    count
+}
@@ -0,0 +1,30 @@
+use specs::prelude::*;
+use super::{ BlocksTile, Map, Position };
+
+/// Rebuilds `Map::blocked` and `Map::tile_content` from the current
+/// entity positions each turn, so movement and pathing see this turn's
+/// occupants rather than stale data.
+pub struct MapIndexingSystem {}
+
+impl<'a> System<'a> for MapIndexingSystem {
+    type SystemData = ( WriteExpect<'a, Map>,
+                         ReadStorage<'a, Position>,
+                         ReadStorage<'a, BlocksTile>,
+                         Entities<'a> );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (mut map, position, blockers, entities) = data;
+
+        map.populate_blocked();
+        map.clear_content_index();
+        for (entity, position) in (&entities, &position).join() {
+            let idx = map.xy_idx(position.x, position.y);
+
+            if blockers.get(entity).is_some() {
+                map.blocked[idx] = true;
+            }
+
+            map.tile_content[idx].push(entity);
+        }
+    }
+}
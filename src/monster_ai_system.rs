@@ -0,0 +1,52 @@
+use specs::prelude::*;
+use rltk::{ DistanceAlg, Point };
+use super::{ Map, Monster, Position, RunState, Viewshed, WantsToMelee };
+
+/// While it's `RunState::MonsterTurn`, every monster either files a melee
+/// intent against an adjacent player or, if the player is in sight but not
+/// adjacent, takes one A* step toward them.
+pub struct MonsterAI {}
+
+impl<'a> System<'a> for MonsterAI {
+    type SystemData = ( WriteExpect<'a, Map>,
+                         ReadExpect<'a, Point>,
+                         ReadExpect<'a, Entity>,
+                         ReadExpect<'a, RunState>,
+                         Entities<'a>,
+                         WriteStorage<'a, Viewshed>,
+                         ReadStorage<'a, Monster>,
+                         WriteStorage<'a, Position>,
+                         WriteStorage<'a, WantsToMelee> );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (mut map, player_pos, player_entity, runstate, entities, mut viewshed, monster, mut position, mut wants_to_melee) = data;
+
+        if *runstate != RunState::MonsterTurn { return; }
+
+        // The player's tile is blocked by its `BlocksTile`, and A* only ever
+        // reaches tiles listed as available exits, so open it for the searches.
+        let player_idx = map.xy_idx(player_pos.x, player_pos.y);
+        map.blocked[player_idx] = false;
+
+        for (entity, viewshed, _monster, pos) in (&entities, &mut viewshed, &monster, &mut position).join() {
+            let distance = DistanceAlg::Pythagoras.distance2d(Point::new(pos.x, pos.y), *player_pos);
+            if distance < 1.5 {
+                wants_to_melee.insert(entity, WantsToMelee { target: *player_entity })
+                    .expect("Unable to insert melee intent");
+            } else if viewshed.visible_tiles.contains(&*player_pos) {
+                let path = rltk::a_star_search(
+                    map.xy_idx(pos.x, pos.y),
+                    map.xy_idx(player_pos.x, player_pos.y),
+                    &mut *map,
+                );
+                if path.success && path.steps.len() > 1 {
+                    pos.x = path.steps[1] as i32 % map.width;
+                    pos.y = path.steps[1] as i32 / map.width;
+                    viewshed.dirty = true;
+                }
+            }
+        }
+
+        map.blocked[player_idx] = true;
+    }
+}
@@ -0,0 +1,68 @@
+use rltk::{ Point, Rltk, VirtualKeyCode };
+use specs::prelude::*;
+use super::{ CombatStats, Map, Player, Position, RunState, State, Viewshed, WantsToMelee };
+
+pub fn try_move_player(delta_x: i32, delta_y: i32, ecs: &mut World) {
+    let map = ecs.fetch::<Map>();
+
+    let (player_entity, dest_x, dest_y) = {
+        let entities = ecs.entities();
+        let players = ecs.read_storage::<Player>();
+        let positions = ecs.read_storage::<Position>();
+        (&entities, &players, &positions).join()
+            .map(|(entity, _player, pos)| (entity, pos.x + delta_x, pos.y + delta_y))
+            .next()
+            .expect("no player entity")
+    };
+
+    if dest_x < 0 || dest_x > map.width - 1 || dest_y < 0 || dest_y > map.height - 1 {
+        return;
+    }
+
+    let destination_idx = map.xy_idx(dest_x, dest_y);
+
+    // Anything with combat stats standing on the destination tile is a
+    // fight, not a move.
+    let target = {
+        let combat_stats = ecs.read_storage::<CombatStats>();
+        map.tile_content[destination_idx].iter()
+            .find(|entity| combat_stats.get(**entity).is_some())
+            .copied()
+    };
+
+    if let Some(target) = target {
+        let mut wants_to_melee = ecs.write_storage::<WantsToMelee>();
+        wants_to_melee.insert(player_entity, WantsToMelee { target })
+            .expect("Unable to insert melee intent");
+        return;
+    }
+
+    if !map.blocked[destination_idx] {
+        let mut positions = ecs.write_storage::<Position>();
+        let mut viewsheds = ecs.write_storage::<Viewshed>();
+        let pos = positions.get_mut(player_entity).unwrap();
+        pos.x = dest_x;
+        pos.y = dest_y;
+
+        let viewshed = viewsheds.get_mut(player_entity).unwrap();
+        viewshed.dirty = true;
+
+        let mut player_pos = ecs.write_resource::<Point>();
+        player_pos.x = pos.x;
+        player_pos.y = pos.y;
+    }
+}
+
+pub fn player_input(gs: &mut State, ctx: &mut Rltk) -> RunState {
+    match ctx.key {
+        None => return RunState::AwaitingInput,
+        Some(key) => match key {
+            VirtualKeyCode::Left => try_move_player(-1, 0, &mut gs.ecs),
+            VirtualKeyCode::Right => try_move_player(1, 0, &mut gs.ecs),
+            VirtualKeyCode::Up => try_move_player(0, -1, &mut gs.ecs),
+            VirtualKeyCode::Down => try_move_player(0, 1, &mut gs.ecs),
+            _ => return RunState::AwaitingInput,
+        },
+    }
+    RunState::PlayerTurn
+}
@@ -0,0 +1,32 @@
+use rltk::RandomNumberGenerator;
+
+/// Wraps a single `RandomNumberGenerator` as an ECS resource, so every
+/// system draws from the same stream instead of each spinning up its own.
+/// Seeding it makes a whole run (dungeon layout, future spawns, ...)
+/// reproducible.
+pub struct RngResource {
+    rng: RandomNumberGenerator,
+}
+
+impl RngResource {
+    pub fn new(seed: Option<u64>) -> RngResource {
+        RngResource {
+            rng: match seed {
+                Some(seed) => RandomNumberGenerator::seeded(seed),
+                None => RandomNumberGenerator::new(),
+            },
+        }
+    }
+
+    pub fn roll_dice(&mut self, n: i32, die: i32) -> i32 {
+        self.rng.roll_dice(n, die)
+    }
+
+    pub fn range(&mut self, min: i32, max: i32) -> i32 {
+        self.rng.range(min, max)
+    }
+
+    pub fn reseed(&mut self, seed: u64) {
+        self.rng = RandomNumberGenerator::seeded(seed);
+    }
+}
@@ -0,0 +1,124 @@
+use specs::prelude::*;
+use rltk::Point;
+use super::{ Viewshed, Position, Map, TileType, Player };
+
+/// Recomputes each dirty `Viewshed` using symmetric recursive shadowcasting,
+/// then (for the player) reveals and lights up the tiles it can see.
+pub struct VisibilitySystem {}
+
+impl<'a> System<'a> for VisibilitySystem {
+    type SystemData = ( WriteExpect<'a, Map>,
+                         Entities<'a>,
+                         WriteStorage<'a, Viewshed>,
+                         WriteStorage<'a, Position>,
+                         ReadStorage<'a, Player> );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (mut map, entities, mut viewshed, pos, player) = data;
+
+        for (ent, viewshed, pos) in (&entities, &mut viewshed, &pos).join() {
+            if !viewshed.dirty { continue; }
+            viewshed.dirty = false;
+            viewshed.visible_tiles.clear();
+            viewshed.visible_tiles.push(Point::new(pos.x, pos.y));
+            compute_fov(&map, pos.x, pos.y, viewshed.range, &mut viewshed.visible_tiles);
+            viewshed.visible_tiles.retain(|p| p.x >= 0 && p.x < map.width && p.y >= 0 && p.y < map.height);
+
+            if player.get(ent).is_some() {
+                for vis in map.visible_tiles.iter_mut() { *vis = false; }
+                for vis in viewshed.visible_tiles.iter() {
+                    let idx = map.xy_idx(vis.x, vis.y);
+                    map.revealed_tiles[idx] = true;
+                    map.visible_tiles[idx] = true;
+                }
+            }
+        }
+    }
+}
+
+/// Multipliers that rotate/reflect an octant's (row, col) scan onto the
+/// eight true octants around the origin.
+const OCTANTS: [(i32, i32, i32, i32); 8] = [
+    (1, 0, 0, -1),
+    (0, 1, -1, 0),
+    (0, -1, -1, 0),
+    (1, 0, 0, 1),
+    (-1, 0, 0, 1),
+    (0, -1, 1, 0),
+    (0, 1, 1, 0),
+    (-1, 0, 0, -1),
+];
+
+fn compute_fov(map: &Map, origin_x: i32, origin_y: i32, range: i32, visible: &mut Vec<Point>) {
+    for &(xx, xy, yx, yy) in OCTANTS.iter() {
+        cast_octant(map, origin_x, origin_y, range, 1, 1.0, 0.0, xx, xy, yx, yy, visible);
+    }
+}
+
+/// Scans one octant row by row, tracking the slope interval of sky that is
+/// still unblocked. A cell at (row, col) occupies the slope range
+/// `[(col-0.5)/row, (col+0.5)/row]`; walls shrink the open interval and, if
+/// they split it, recurse into the two halves separately.
+#[allow(clippy::too_many_arguments)]
+fn cast_octant(
+    map: &Map,
+    origin_x: i32,
+    origin_y: i32,
+    range: i32,
+    row: i32,
+    mut start_slope: f32,
+    end_slope: f32,
+    xx: i32,
+    xy: i32,
+    yx: i32,
+    yy: i32,
+    visible: &mut Vec<Point>,
+) {
+    if start_slope < end_slope { return; }
+
+    let mut blocked = false;
+    let mut next_start_slope = start_slope;
+
+    for distance in row..=range {
+        if blocked { break; }
+
+        let dy = -distance;
+        for dx in -distance..=0 {
+            let left_slope = (dx as f32 - 0.5) / dy as f32;
+            let right_slope = (dx as f32 + 0.5) / dy as f32;
+
+            if start_slope < right_slope { continue; }
+            if end_slope > left_slope { break; }
+
+            let map_x = origin_x + dx * xx + dy * xy;
+            let map_y = origin_y + dx * yx + dy * yy;
+
+            if map_x < 0 || map_x >= map.width || map_y < 0 || map_y >= map.height { continue; }
+
+            let idx = map.xy_idx(map_x, map_y);
+            let is_wall = map.tiles[idx] == TileType::Wall;
+
+            // A floor is lit only if its centre lies inside the open interval,
+            // which keeps visibility symmetric; walls are lit whenever reached.
+            let col = (-dx) as f32;
+            let symmetric = col >= distance as f32 * end_slope && col <= distance as f32 * start_slope;
+            if (is_wall || symmetric) && dx * dx + dy * dy <= range * range {
+                visible.push(Point::new(map_x, map_y));
+            }
+
+            if blocked {
+                if is_wall {
+                    next_start_slope = right_slope;
+                    continue;
+                } else {
+                    blocked = false;
+                    start_slope = next_start_slope;
+                }
+            } else if is_wall && distance < range {
+                blocked = true;
+                next_start_slope = right_slope;
+                cast_octant(map, origin_x, origin_y, range, distance + 1, start_slope, left_slope, xx, xy, yx, yy, visible);
+            }
+        }
+    }
+}